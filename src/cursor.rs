@@ -0,0 +1,93 @@
+//! A small bounds-checked byte reader used while parsing TZif files, so
+//! truncated or malformed input produces an `Err` instead of an index panic.
+
+use anyhow::{anyhow, Result};
+
+pub(crate) struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// The bytes from the current position to the end of the input.
+    pub(crate) fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    pub(crate) fn read_exact(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .ok_or_else(|| anyhow!("length overflow reading {} bytes", n))?;
+        let slice = self.bytes.get(self.pos..end).ok_or_else(|| {
+            anyhow!(
+                "unexpected end of input: wanted {} byte(s) at offset {}, only {} remain",
+                n,
+                self.pos,
+                self.bytes.len().saturating_sub(self.pos)
+            )
+        })?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read_exact(1)?[0])
+    }
+
+    pub(crate) fn read_be_i32(&mut self) -> Result<i32> {
+        let bytes: [u8; 4] = self.read_exact(4)?.try_into().expect("read_exact(4)");
+        Ok(i32::from_be_bytes(bytes))
+    }
+
+    pub(crate) fn read_be_i64(&mut self) -> Result<i64> {
+        let bytes: [u8; 8] = self.read_exact(8)?.try_into().expect("read_exact(8)");
+        Ok(i64::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_values_and_advances_position() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+        let mut cursor = Cursor::new(&bytes);
+
+        assert_eq!(cursor.read_u8().unwrap(), 0x01);
+        assert_eq!(cursor.read_be_i32().unwrap(), 0x02030405);
+        assert_eq!(cursor.remaining(), &[0x06, 0x07, 0x08, 0x09]);
+        assert_eq!(cursor.read_exact(2).unwrap(), &[0x06, 0x07]);
+        assert_eq!(cursor.remaining(), &[0x08, 0x09]);
+    }
+
+    #[test]
+    fn read_be_i64_needs_eight_bytes() {
+        let bytes = [0u8; 8];
+        let mut cursor = Cursor::new(&bytes);
+
+        assert_eq!(cursor.read_be_i64().unwrap(), 0);
+        assert!(cursor.remaining().is_empty());
+    }
+
+    #[test]
+    fn read_exact_errs_on_truncated_input_instead_of_panicking() {
+        let bytes = [0xAAu8, 0xBB];
+        let mut cursor = Cursor::new(&bytes);
+
+        assert!(cursor.read_exact(3).is_err());
+        // A failed read must not consume any bytes.
+        assert_eq!(cursor.remaining(), &bytes);
+    }
+
+    #[test]
+    fn read_u8_errs_on_empty_input() {
+        let mut cursor = Cursor::new(&[]);
+        assert!(cursor.read_u8().is_err());
+    }
+}
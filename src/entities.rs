@@ -1,38 +1,201 @@
 use std::fmt::Display;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
+
+use crate::cursor::Cursor;
+use crate::posix_tz::TransitionRule;
 
 #[derive(Debug)]
 pub struct TzFile {
     pub header: TzFileHeader,
     pub body: TzFileBody,
+    // Present for version '2', '3' and '4' files: a second header/body pair
+    // carrying 64-bit transition times, laid out right after the V1 block.
+    pub header_v2: Option<TzFileHeader>,
+    pub body_v2: Option<TzFileBody>,
 }
 
 impl TzFile {
     pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
-        let bytes_count = bytes.len();
-        if bytes_count < 44 {
-            return Err(anyhow!("invalid header size '{bytes_count}'"));
+        let mut cursor = Cursor::new(bytes);
+
+        let header =
+            TzFileHeader::from_cursor(&mut cursor).map_err(|err| anyhow!("header is invalid: {}", err))?;
+        let body = TzFileBody::from_cursor(&mut cursor, &header, false)
+            .map_err(|err| anyhow!("invalid bytes for Tz File body: {}", err))?;
+
+        if header.version == '\0' {
+            return Ok(Self {
+                header,
+                body,
+                header_v2: None,
+                body_v2: None,
+            });
         }
 
-        let mut header_bytes = [0; 44];
-        for (idx, byte) in bytes.iter().take(44).enumerate() {
-            header_bytes[idx] = *byte;
+        // Version 2/3/4: a second "TZif" header + body follow, this time
+        // with 8-byte transition times wide enough to survive 2038.
+        let header_v2 = TzFileHeader::from_cursor(&mut cursor)
+            .map_err(|err| anyhow!("V2+ header is invalid: {}", err))?;
+        let mut body_v2 = TzFileBody::from_cursor(&mut cursor, &header_v2, true)
+            .map_err(|err| anyhow!("invalid bytes for V2+ Tz File body: {}", err))?;
+        body_v2.footer =
+            parse_tz_string_footer(cursor.remaining()).context("invalid TZ string footer")?;
+
+        Ok(Self {
+            header,
+            body,
+            header_v2: Some(header_v2),
+            body_v2: Some(body_v2),
+        })
+    }
+
+    /// The header describing the data this crate actually surfaces: the
+    /// V2+ header when present, the V1 header otherwise.
+    pub fn active_header(&self) -> &TzFileHeader {
+        self.header_v2.as_ref().unwrap_or(&self.header)
+    }
+
+    /// The body describing the data this crate actually surfaces: the
+    /// wider, authoritative V2+ body when present, the V1 body otherwise.
+    pub fn active_body(&self) -> &TzFileBody {
+        self.body_v2.as_ref().unwrap_or(&self.body)
+    }
+
+    /// Converts a stored "unix leap time" (the domain `tt_trans` and
+    /// `leap_pairs` occurrences are expressed in on a leap-aware file) to a
+    /// true Unix timestamp, by subtracting the leap-second correction in
+    /// effect at `leap_time`. A no-op when the file carries no leap seconds.
+    ///
+    /// A `leap_time` that lands exactly on an inserted (positive) leap
+    /// second has no real Unix-time equivalent (it's the repeated
+    /// `23:59:60`); this clamps it to the Unix second the leap second
+    /// occurs on.
+    pub fn unix_time(&self, leap_time: i64) -> i64 {
+        let corr = correction_at_or_before(&self.active_body().leap_pairs, leap_time);
+        leap_time - corr as i64
+    }
+
+    /// Inverse of `unix_time`: converts a true Unix timestamp to the
+    /// corresponding "unix leap time".
+    pub fn leap_time(&self, unix_time: i64) -> i64 {
+        let leap_pairs = &self.active_body().leap_pairs;
+        let idx = leap_pairs.partition_point(|&(occurrence, corr)| occurrence - corr as i64 <= unix_time);
+        let corr = if idx == 0 { 0 } else { leap_pairs[idx - 1].1 };
+        unix_time + corr as i64
+    }
+
+    /// Finds the `TTInfo` in effect at `unix_time`: the type of the last
+    /// transition at or before `unix_time`; the POSIX footer rule (or the
+    /// first standard-time type, if there's no usable footer) when
+    /// `unix_time` follows the last transition (or the file has none); or
+    /// just the first standard-time type, used as-is, when `unix_time`
+    /// precedes every transition.
+    ///
+    /// The footer is only consulted on the "follows the last transition"
+    /// side: per RFC 8536 §3.2 it describes how offsets continue *past*
+    /// the last transition, and says nothing about times before the
+    /// file's first transition, which real `localtime` implementations
+    /// resolve to the first standard-time entry unchanged.
+    ///
+    /// `tt_trans` is compared in the leap-time domain, so this is correct
+    /// for leap-aware files too; `leap_time` is a no-op without leap
+    /// seconds, so non-leap-aware files behave exactly as before.
+    pub fn find_local_time_type(&self, unix_time: i64) -> anyhow::Result<&TTInfo> {
+        let body = self.active_body();
+        let search_time = self.leap_time(unix_time);
+
+        match (body.tt_trans.first(), body.tt_trans.last()) {
+            (Some(&first), Some(&last)) if (first..=last).contains(&search_time) => {
+                let idx = match body.tt_trans.binary_search(&search_time) {
+                    Ok(idx) => idx,
+                    Err(idx) => idx - 1,
+                };
+                let ttinfo_idx = body.ttinfo_indices[idx];
+                body.ttinfo_entries
+                    .get(ttinfo_idx as usize)
+                    .ok_or_else(|| anyhow!("ttinfo index '{}' is out of range", ttinfo_idx))
+            }
+            (Some(&first), _) if search_time < first => self.first_standard_time_type(),
+            _ => self.fallback_time_type(unix_time),
+        }
+    }
+
+    /// The abbreviation (e.g. `"EST"`) in effect at `unix_time`.
+    pub fn find_abbreviation(&self, unix_time: i64) -> anyhow::Result<&str> {
+        let ttinfo = self.find_local_time_type(unix_time)?;
+        self.active_body().designation(ttinfo)
+    }
+
+    /// The type to use past the last transition (or for a file with no
+    /// transitions at all): the POSIX footer rule if there is one and it
+    /// names a known type, the first standard-time type otherwise.
+    fn fallback_time_type(&self, unix_time: i64) -> anyhow::Result<&TTInfo> {
+        let body = self.active_body();
+
+        if let Some(rule) = &body.footer {
+            let (want_offset, want_dst) = if rule.is_dst_active(unix_time) {
+                (rule.dst.as_ref().expect("is_dst_active implies dst").offset, true)
+            } else {
+                (rule.std_offset, false)
+            };
+
+            if let Some(ttinfo) = body
+                .ttinfo_entries
+                .iter()
+                .find(|tt| tt.tt_utoff == want_offset && tt.tt_isdst == want_dst)
+            {
+                return Ok(ttinfo);
+            }
         }
 
-        let header = match TzFileHeader::try_from(header_bytes) {
-            Ok(header) => header,
-            Err(err) => return Err(anyhow!("header is invalid: {}", err)),
-        };
+        self.first_standard_time_type()
+    }
 
-        let body = match TzFileBody::from_bytes_and_header(&bytes[44..], &header) {
-            Ok(body) => body,
-            Err(err) => return Err(anyhow!("invalid bytes for Tz File body: {}", err)),
-        };
-        Ok(Self { header, body })
+    /// The first standard-time (non-DST) type, or the first type at all if
+    /// every type in the file is DST.
+    fn first_standard_time_type(&self) -> anyhow::Result<&TTInfo> {
+        let body = self.active_body();
+        body.ttinfo_entries
+            .iter()
+            .find(|tt| !tt.tt_isdst)
+            .or_else(|| body.ttinfo_entries.first())
+            .ok_or_else(|| anyhow!("tz file has no time types"))
     }
 }
 
+/// The accumulated leap-second correction (`corr` of the last `leap_pairs`
+/// entry whose `occurrence` is `<= leap_time`), or 0 before the first entry.
+fn correction_at_or_before(leap_pairs: &[(i64, i32)], leap_time: i64) -> i32 {
+    let idx = leap_pairs.partition_point(|&(occurrence, _)| occurrence <= leap_time);
+    if idx == 0 {
+        0
+    } else {
+        leap_pairs[idx - 1].1
+    }
+}
+
+/// Parses the `\n<TZ string>\n` footer that trails a V2+ body, if any bytes
+/// remain. Returns `None` for an empty footer (`\n\n`).
+fn parse_tz_string_footer(bytes: &[u8]) -> anyhow::Result<Option<TransitionRule>> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+
+    let text = std::str::from_utf8(bytes).context("TZ string footer is not valid UTF-8")?;
+    let inner = text
+        .strip_prefix('\n')
+        .ok_or_else(|| anyhow!("TZ string footer must start with a newline"))?
+        .strip_suffix('\n')
+        .ok_or_else(|| anyhow!("TZ string footer must end with a newline"))?;
+
+    if inner.is_empty() {
+        return Ok(None);
+    }
+
+    TransitionRule::parse(inner).map(Some)
+}
+
 #[derive(Debug)]
 pub struct TzFileHeader {
     magic: String,
@@ -47,11 +210,9 @@ pub struct TzFileHeader {
     tzh_charcnt: i32,
 }
 
-impl TryFrom<[u8; 44]> for TzFileHeader {
-    type Error = anyhow::Error;
-
-    fn try_from(value: [u8; 44]) -> Result<Self, Self::Error> {
-        let magic = String::from_utf8_lossy(&value[0..4]).into_owned();
+impl TzFileHeader {
+    fn from_cursor(cursor: &mut Cursor) -> anyhow::Result<Self> {
+        let magic = String::from_utf8_lossy(cursor.read_exact(4)?).into_owned();
         if magic != "TZif" {
             return Err(anyhow!(
                 "invalid TZ Info magic header '{}', expected 'TZif'",
@@ -59,20 +220,22 @@ impl TryFrom<[u8; 44]> for TzFileHeader {
             ));
         }
 
+        let version = cursor.read_u8()? as char;
+        let reserved = cursor.read_exact(15)?;
+        let mut _reserved = [0u8; 15];
+        _reserved.copy_from_slice(reserved);
+
         Ok(Self {
             magic,
-            version: value[4] as char,
-            _reserved: [
-                value[5], value[6], value[7], value[8], value[9], value[10], value[11], value[12],
-                value[13], value[14], value[15], value[16], value[17], value[18], value[19],
-            ],
-
-            tzh_ttisutcnt: i32::from_be_bytes([value[20], value[21], value[22], value[23]]),
-            tzh_ttisstdcnt: i32::from_be_bytes([value[24], value[25], value[26], value[27]]),
-            tzh_leapcnt: i32::from_be_bytes([value[28], value[29], value[30], value[31]]),
-            tzh_timecnt: i32::from_be_bytes([value[32], value[33], value[34], value[35]]),
-            tzh_typecnt: i32::from_be_bytes([value[36], value[37], value[38], value[39]]),
-            tzh_charcnt: i32::from_be_bytes([value[40], value[41], value[42], value[43]]),
+            version,
+            _reserved,
+
+            tzh_ttisutcnt: cursor.read_be_i32()?,
+            tzh_ttisstdcnt: cursor.read_be_i32()?,
+            tzh_leapcnt: cursor.read_be_i32()?,
+            tzh_timecnt: cursor.read_be_i32()?,
+            tzh_typecnt: cursor.read_be_i32()?,
+            tzh_charcnt: cursor.read_be_i32()?,
         })
     }
 }
@@ -85,79 +248,98 @@ impl Display for TzFileHeader {
 
 #[derive(Debug)]
 pub struct TzFileBody {
-    pub tt_trans: Vec<i32>, // be
+    pub tt_trans: Vec<i64>, // be
     pub ttinfo_indices: Vec<u8>,
     pub ttinfo_entries: Vec<TTInfo>, // be
     pub tz_designations: String,     // null terminated strs
-    pub leap_pairs: Vec<(i32, i32)>, // be
+    pub leap_pairs: Vec<(i64, i32)>, // be
     pub std_indicators: Vec<bool>,
     pub ut_indicators: Vec<bool>,
+    // Only ever set on the V2+ body, by `TzFile::from_bytes`: the fixed-size
+    // layout `TzFileBody::from_cursor` parses doesn't include the trailing TZ string.
+    pub footer: Option<TransitionRule>,
 }
 
 impl TzFileBody {
-    fn from_bytes_and_header(bytes: &[u8], header: &TzFileHeader) -> anyhow::Result<Self> {
-        let mut left_idx = 0usize;
-        let trans = bytes[left_idx..left_idx + header.tzh_timecnt as usize * size_of::<i32>()]
-            .chunks(size_of::<i32>())
-            .map(|chunk| i32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-            .collect();
-        left_idx += header.tzh_timecnt as usize * size_of::<i32>();
+    /// Parses the data block following a header. `wide` selects between the
+    /// 4-byte (V1) and 8-byte (V2+) transition/leap-pair occurrence widths;
+    /// both are widened to `i64` here so callers don't need to care which
+    /// block a value came from.
+    fn from_cursor(cursor: &mut Cursor, header: &TzFileHeader, wide: bool) -> anyhow::Result<Self> {
+        let timecnt = header.tzh_timecnt as usize;
+        let typecnt = header.tzh_typecnt as usize;
+        let charcnt = header.tzh_charcnt as usize;
+        let leapcnt = header.tzh_leapcnt as usize;
+
+        let tt_trans = (0..timecnt)
+            .map(|_| {
+                if wide {
+                    cursor.read_be_i64()
+                } else {
+                    cursor.read_be_i32().map(|v| v as i64)
+                }
+            })
+            .collect::<anyhow::Result<Vec<i64>>>()?;
 
-        let ttinfo_indices = bytes[left_idx..left_idx + header.tzh_timecnt as usize].to_vec();
-        left_idx += header.tzh_timecnt as usize;
+        let ttinfo_indices = cursor.read_exact(timecnt)?.to_vec();
 
         let ttinfo_size_unpadded = size_of::<TTInfo>() - 2; // the padding is 2 for ttinfo
-        let ttinfo_entries = bytes
-            [left_idx..left_idx + header.tzh_typecnt as usize * ttinfo_size_unpadded]
-            .chunks(ttinfo_size_unpadded) // each ttinfo struct contains 6 bytes
-            .flat_map(TTInfo::from_bytes)
-            .collect();
-        left_idx += header.tzh_typecnt as usize * ttinfo_size_unpadded;
+        let ttinfo_entries = (0..typecnt)
+            .map(|_| TTInfo::from_bytes(cursor.read_exact(ttinfo_size_unpadded)?))
+            .collect::<anyhow::Result<Vec<TTInfo>>>()?;
 
-        let designations =
-            String::from_utf8_lossy(&bytes[left_idx..left_idx + header.tzh_charcnt as usize])
-                .into_owned();
+        let designations = String::from_utf8_lossy(cursor.read_exact(charcnt)?).into_owned();
         // .split(|&b| b == b'\0')
         // .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
         // .collect::<Vec<String>>()
         // .join("\0");
         // designations.strip_suffix('\0');
         // designations.pop(); // due to split, the last string in the vec will be an empty string because we split on the null terminator and the last string is split into 2
-        left_idx += header.tzh_charcnt as usize;
-
-        let leap_pairs = bytes
-            [left_idx..left_idx + header.tzh_leapcnt as usize * size_of::<(i32, i32)>()]
-            .chunks(size_of::<(i32, i32)>()) // a pair contains 2,  4-byte values
-            .map(|chunk| {
-                (
-                    i32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]),
-                    i32::from_be_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]),
-                )
+
+        let leap_pairs = (0..leapcnt)
+            .map(|_| {
+                let occurrence = if wide {
+                    cursor.read_be_i64()?
+                } else {
+                    cursor.read_be_i32()? as i64
+                };
+                let corr = cursor.read_be_i32()?;
+                Ok((occurrence, corr))
             })
-            .collect();
-        left_idx += header.tzh_leapcnt as usize * size_of::<(i32, i32)>();
+            .collect::<anyhow::Result<Vec<(i64, i32)>>>()?;
 
-        let std_indicators = bytes[left_idx..left_idx + header.tzh_ttisstdcnt as usize]
+        let std_indicators = cursor
+            .read_exact(header.tzh_ttisstdcnt as usize)?
             .iter()
             .map(|&b| b == 1)
             .collect();
-        left_idx += header.tzh_ttisstdcnt as usize;
 
-        let ut_indicators = bytes[left_idx..left_idx + header.tzh_ttisutcnt as usize]
+        let ut_indicators = cursor
+            .read_exact(header.tzh_ttisutcnt as usize)?
             .iter()
             .map(|&b| b == 1)
             .collect();
 
         Ok(Self {
-            tt_trans: trans,
+            tt_trans,
             ttinfo_indices,
             ttinfo_entries,
             tz_designations: designations,
             leap_pairs,
             std_indicators,
             ut_indicators,
+            footer: None,
         })
     }
+
+    /// Resolves a `TTInfo`'s `tt_desigidx` to its abbreviation, e.g. `"EST"`.
+    fn designation(&self, ttinfo: &TTInfo) -> anyhow::Result<&str> {
+        self.tz_designations
+            .get(ttinfo.tt_desigidx as usize..)
+            .and_then(|s| s.split_once('\0'))
+            .map(|(name, _)| name)
+            .ok_or_else(|| anyhow!("designation index '{}' is out of range", ttinfo.tt_desigidx))
+    }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq)]
@@ -184,3 +366,170 @@ impl TTInfo {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_bytes(
+        version: u8,
+        ttisutcnt: i32,
+        ttisstdcnt: i32,
+        leapcnt: i32,
+        timecnt: i32,
+        typecnt: i32,
+        charcnt: i32,
+    ) -> Vec<u8> {
+        let mut b = b"TZif".to_vec();
+        b.push(version);
+        b.extend_from_slice(&[0u8; 15]);
+        for count in [ttisutcnt, ttisstdcnt, leapcnt, timecnt, typecnt, charcnt] {
+            b.extend_from_slice(&count.to_be_bytes());
+        }
+        b
+    }
+
+    fn ttinfo_bytes(utoff: i32, isdst: bool, desigidx: u8) -> Vec<u8> {
+        let mut b = utoff.to_be_bytes().to_vec();
+        b.push(isdst as u8);
+        b.push(desigidx);
+        b
+    }
+
+    /// A minimal V2 TZif file with one V1 transition, one wide V2 transition
+    /// (past `i32::MAX`, to prove the 64-bit block is actually used), two
+    /// time types ("STD"/"DST") and a `STD0DST,M3.2.0,M11.1.0` footer.
+    pub(super) fn sample_v2_tzif() -> Vec<u8> {
+        let mut bytes = header_bytes(b'2', 0, 0, 0, 1, 2, 8);
+        bytes.extend_from_slice(&(-100i32).to_be_bytes());
+        bytes.push(0);
+        bytes.extend(ttinfo_bytes(0, false, 0));
+        bytes.extend(ttinfo_bytes(3600, true, 4));
+        bytes.extend_from_slice(b"STD\0DST\0");
+
+        bytes.extend(header_bytes(b'2', 0, 0, 0, 1, 2, 8));
+        bytes.extend_from_slice(&2_200_000_000i64.to_be_bytes());
+        bytes.push(0);
+        bytes.extend(ttinfo_bytes(0, false, 0));
+        bytes.extend(ttinfo_bytes(3600, true, 4));
+        bytes.extend_from_slice(b"STD\0DST\0");
+        bytes.extend_from_slice(b"\nSTD0DST,M3.2.0,M11.1.0\n");
+        bytes
+    }
+
+    /// A V2 TZif file with no V1 transitions, two V2 transitions (to EST at
+    /// t=100, to EDT at t=200) and an `EST5EDT,M3.2.0,
+    /// M11.1.0` footer, for exercising `find_local_time_type`'s three
+    /// regions: between transitions, before the first one, and after the
+    /// last one.
+    fn sample_lookup_tzif() -> Vec<u8> {
+        let mut bytes = header_bytes(b'2', 0, 0, 0, 0, 0, 0);
+
+        bytes.extend(header_bytes(b'2', 0, 0, 0, 2, 2, 8));
+        bytes.extend_from_slice(&100i64.to_be_bytes());
+        bytes.extend_from_slice(&200i64.to_be_bytes());
+        bytes.push(0);
+        bytes.push(1);
+        bytes.extend(ttinfo_bytes(-18_000, false, 0));
+        bytes.extend(ttinfo_bytes(-14_400, true, 4));
+        bytes.extend_from_slice(b"EST\0EDT\0");
+        bytes.extend_from_slice(b"\nEST5EDT,M3.2.0,M11.1.0\n");
+        bytes
+    }
+
+    #[test]
+    fn find_local_time_type_uses_the_table_between_transitions() {
+        let tzif = TzFile::from_bytes(&sample_lookup_tzif()).expect("valid sample file");
+
+        let ttinfo = tzif.find_local_time_type(150).expect("a type for t=150");
+        assert_eq!(ttinfo.tt_utoff, -18_000);
+        assert!(!ttinfo.tt_isdst);
+        assert_eq!(tzif.find_abbreviation(150).unwrap(), "EST");
+    }
+
+    #[test]
+    fn find_local_time_type_consults_the_footer_past_the_last_transition() {
+        let tzif = TzFile::from_bytes(&sample_lookup_tzif()).expect("valid sample file");
+
+        // 1970-07-01: past t=200, and within the footer's DST window.
+        let ttinfo = tzif
+            .find_local_time_type(15_638_400)
+            .expect("a type past the last transition");
+        assert_eq!(ttinfo.tt_utoff, -14_400);
+        assert!(ttinfo.tt_isdst);
+        assert_eq!(tzif.find_abbreviation(15_638_400).unwrap(), "EDT");
+    }
+
+    #[test]
+    fn find_local_time_type_ignores_the_footer_before_the_first_transition() {
+        let tzif = TzFile::from_bytes(&sample_lookup_tzif()).expect("valid sample file");
+
+        // 1969-07-01: before t=100. Naively extrapolating the footer
+        // backward would call this DST (it's within the M3.2.0..M11.1.0
+        // window), but RFC 8536 only extrapolates past the *last*
+        // transition, so this must resolve to the first standard-time type.
+        let ttinfo = tzif
+            .find_local_time_type(-15_897_600)
+            .expect("a type before the first transition");
+        assert_eq!(ttinfo.tt_utoff, -18_000);
+        assert!(!ttinfo.tt_isdst);
+        assert_eq!(tzif.find_abbreviation(-15_897_600).unwrap(), "EST");
+    }
+
+    #[test]
+    fn parses_v2_block_with_64_bit_transitions() {
+        let tzif = TzFile::from_bytes(&sample_v2_tzif()).expect("valid sample file");
+
+        assert_eq!(tzif.body.tt_trans, vec![-100]);
+        assert_eq!(tzif.header.version, '2');
+
+        let body_v2 = tzif.body_v2.as_ref().expect("a V2 body");
+        // 2_200_000_000 doesn't fit in an i32 (max ~2.1B): this only round-trips
+        // correctly if the wide 8-byte field was actually read.
+        assert_eq!(body_v2.tt_trans, vec![2_200_000_000]);
+        assert!(i32::try_from(2_200_000_000i64).is_err());
+        assert_eq!(tzif.active_body().tt_trans, vec![2_200_000_000]);
+    }
+
+    /// A minimal V1-only file (no footer) with a single transition, one UTC
+    /// time type, and two leap-second entries: `+1` at occurrence 500 and
+    /// `+2` at occurrence 2000 (unix-leap-time domain).
+    fn sample_tzif_with_leap_pairs() -> Vec<u8> {
+        let mut bytes = header_bytes(0, 0, 0, 2, 1, 1, 4);
+        bytes.extend_from_slice(&1000i32.to_be_bytes()); // tt_trans
+        bytes.push(0); // ttinfo_indices
+        bytes.extend(ttinfo_bytes(0, false, 0));
+        bytes.extend_from_slice(b"UTC\0");
+        bytes.extend_from_slice(&500i32.to_be_bytes());
+        bytes.extend_from_slice(&1i32.to_be_bytes());
+        bytes.extend_from_slice(&2000i32.to_be_bytes());
+        bytes.extend_from_slice(&2i32.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn correction_at_or_before_steps_at_each_leap_pair() {
+        let leap_pairs = vec![(500i64, 1i32), (2000, 2)];
+
+        assert_eq!(correction_at_or_before(&leap_pairs, 100), 0);
+        assert_eq!(correction_at_or_before(&leap_pairs, 500), 1);
+        assert_eq!(correction_at_or_before(&leap_pairs, 1500), 1);
+        assert_eq!(correction_at_or_before(&leap_pairs, 2000), 2);
+        assert_eq!(correction_at_or_before(&leap_pairs, 3000), 2);
+    }
+
+    #[test]
+    fn leap_time_and_unix_time_round_trip() {
+        let tzif = TzFile::from_bytes(&sample_tzif_with_leap_pairs()).expect("valid sample file");
+
+        for unix_time in [100i64, 600, 2500] {
+            let leap_time = tzif.leap_time(unix_time);
+            assert_eq!(tzif.unix_time(leap_time), unix_time);
+        }
+
+        // Before the first leap pair: no correction applied yet.
+        assert_eq!(tzif.leap_time(100), 100);
+        // Past both leap pairs: both corrections have accumulated.
+        assert_eq!(tzif.leap_time(2500), 2502);
+    }
+}
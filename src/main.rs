@@ -1,14 +1,16 @@
 use anyhow::{Context, anyhow};
 use entities::TzFile;
 use std::{
-    collections::HashMap,
     env::args,
     fs::read_dir,
     path::{Path, PathBuf},
     str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
+mod cursor;
 mod entities;
+mod posix_tz;
 
 fn main() {
     let mut zoneinfo =
@@ -19,43 +21,29 @@ fn main() {
         return;
     }
 
+    let query_time = args()
+        .nth(2)
+        .map(|arg| arg.parse::<i64>().expect("unix_time argument to be a valid i64"))
+        .unwrap_or_else(now);
+
     zoneinfo.push(locale);
     match parse_tzif(&zoneinfo) {
         Ok(tzif) => {
-            println!("{:#?}", tzif.body);
+            let body = tzif.active_body();
+            println!("{} ({:#?})", tzif.active_header(), body);
             println!("----------------------------------------------------------------------");
 
-            let mut table: HashMap<String, Timezone> = HashMap::new();
-            for (idx, ttinfo_idx) in tzif.body.ttinfo_indices[..tzif.body.ttinfo_indices.len() - 1] // all but the last indice
-                .iter()
-                .enumerate()
-            {
-                let trans = tzif.body.tt_trans[idx];
-                let ttinfo = &(tzif.body.ttinfo_entries)[*ttinfo_idx as usize];
-                let tz_name = tzif
-                    .body
-                    .tz_designations
-                    .get(ttinfo.tt_desigidx as usize..)
-                    .unwrap()
-                    .split_once('\0')
-                    .unwrap()
-                    .0
-                    .to_string();
-                if let Some(tz) = table.get_mut(&tz_name) {
-                    tz.transitions.push(trans);
-                } else {
-                    table.insert(
-                        tz_name.clone(),
-                        Timezone {
-                            name: tz_name,
-                            ut_offset: ttinfo.tt_utoff,
-                            is_daylight_savings: ttinfo.tt_isdst,
-                            transitions: vec![],
-                        },
+            match tzif.find_local_time_type(query_time) {
+                Ok(ttinfo) => {
+                    let abbr = tzif.find_abbreviation(query_time).unwrap_or("<unknown>");
+                    let leap_time = tzif.leap_time(query_time);
+                    println!(
+                        "at unix_time {query_time}: offset={}s is_dst={} abbreviation={abbr} (leap_time={leap_time}, unix_time={})",
+                        ttinfo.tt_utoff, ttinfo.tt_isdst, tzif.unix_time(leap_time),
                     );
                 }
+                Err(err) => eprintln!("Error: could not resolve unix_time {query_time}: {err}"),
             }
-            println!("{:#?}", table)
         }
         Err(err) => {
             eprintln!("Error: {}: {}", err, err.root_cause());
@@ -63,6 +51,13 @@ fn main() {
     };
 }
 
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time to be after the Unix epoch")
+        .as_secs() as i64
+}
+
 fn walk_dir(dir_path: &Path) {
     let dir = read_dir(dir_path).expect("zoneinfo dir can be read");
     let mut tzs: Vec<TzFile> = Vec::new();
@@ -86,7 +81,7 @@ fn walk_dir(dir_path: &Path) {
     }
 
     for tz in tzs {
-        println!("{:#?}", tz.body);
+        println!("{:#?}", tz.active_body());
     }
 }
 
@@ -99,11 +94,3 @@ fn parse_tzif(file: &Path) -> anyhow::Result<TzFile> {
         Err(err) => Err(err.context(anyhow!("could not parse file '{}' as tzif", file.display()))),
     }
 }
-
-#[derive(Debug, Hash, PartialEq, Eq)]
-struct Timezone {
-    name: String,
-    ut_offset: i32,
-    is_daylight_savings: bool,
-    transitions: Vec<i32>,
-}
@@ -0,0 +1,450 @@
+//! Parser for the POSIX TZ string that version 2+ TZif files append as a
+//! footer, describing the DST rule to apply past the last transition in
+//! `tt_trans`. See `tzset(3)` for the grammar this mirrors.
+
+use anyhow::anyhow;
+
+/// One date specification inside a `TransitionDate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateSpec {
+    /// `Jn`: Julian day 1-365, Feb 29 is never counted.
+    JulianNoLeap(u16),
+    /// `n`: day 0-365, Feb 29 is counted in leap years.
+    Julian(u16),
+    /// `Mm.w.d`: month (1-12), week (1-5, 5 = last), weekday (0-6, 0 = Sunday).
+    MonthWeekDay { month: u8, week: u8, weekday: u8 },
+}
+
+/// A date plus the local time of day (in seconds, may be negative or exceed
+/// 24h) at which a DST transition takes effect. Defaults to 02:00:00 when
+/// the TZ string omits the `/time` part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionDate {
+    pub date: DateSpec,
+    pub time: i64,
+}
+
+const DEFAULT_TRANSITION_TIME: i64 = 2 * 3600;
+
+/// The alternating `std dst start,end` form of a DST rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DstRule {
+    pub name: String,
+    /// Seconds east of UTC, same sign convention as `TTInfo::tt_utoff`.
+    pub offset: i32,
+    pub start: TransitionDate,
+    pub end: TransitionDate,
+}
+
+/// A fully parsed POSIX TZ string, e.g. `EST5EDT,M3.2.0,M11.1.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionRule {
+    pub std_name: String,
+    /// Seconds east of UTC, same sign convention as `TTInfo::tt_utoff`
+    /// (the POSIX string itself uses the opposite sign, e.g. `EST5` is
+    /// UTC-5, so the raw parsed value is negated on the way in).
+    pub std_offset: i32,
+    pub dst: Option<DstRule>,
+}
+
+impl TransitionRule {
+    /// Whether daylight time is in effect at `unix_time` according to this
+    /// rule's `start`/`end` dates, recomputed for whichever year `unix_time`
+    /// falls in. Always `false` when the rule has no DST half.
+    pub fn is_dst_active(&self, unix_time: i64) -> bool {
+        let Some(dst) = &self.dst else {
+            return false;
+        };
+
+        let (year, _, _) = civil_from_days(unix_time.div_euclid(86400));
+        let start = dst.start.date.resolve_day(year) * 86400 + dst.start.time
+            - self.std_offset as i64;
+        let end = dst.end.date.resolve_day(year) * 86400 + dst.end.time - dst.offset as i64;
+
+        if start <= end {
+            unix_time >= start && unix_time < end
+        } else {
+            // Southern-hemisphere rules: DST spans the turn of the year.
+            unix_time >= start || unix_time < end
+        }
+    }
+
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let (std_name, rest) = parse_name(s)?;
+        let (raw_std_offset, rest) = parse_offset(rest)?;
+        let std_offset = -raw_std_offset;
+
+        if rest.is_empty() {
+            return Ok(Self {
+                std_name,
+                std_offset,
+                dst: None,
+            });
+        }
+
+        let (dst_name, rest) = parse_name(rest)?;
+        let (raw_dst_offset, rest) = if rest.starts_with(',') {
+            (raw_std_offset - 3600, rest)
+        } else {
+            parse_offset(rest)?
+        };
+        let dst_offset = -raw_dst_offset;
+
+        let rest = rest
+            .strip_prefix(',')
+            .ok_or_else(|| anyhow!("expected ',' before DST start date in '{}'", s))?;
+        let (start, rest) = parse_transition_date(rest)?;
+        let rest = rest
+            .strip_prefix(',')
+            .ok_or_else(|| anyhow!("expected ',' before DST end date in '{}'", s))?;
+        let (end, rest) = parse_transition_date(rest)?;
+        if !rest.is_empty() {
+            return Err(anyhow!("unexpected trailing characters '{}' in '{}'", rest, s));
+        }
+
+        Ok(Self {
+            std_name,
+            std_offset,
+            dst: Some(DstRule {
+                name: dst_name,
+                offset: dst_offset,
+                start,
+                end,
+            }),
+        })
+    }
+}
+
+impl DateSpec {
+    /// Resolves this date spec to a day count since the Unix epoch, for the
+    /// given proleptic-Gregorian `year`.
+    fn resolve_day(&self, year: i64) -> i64 {
+        match *self {
+            DateSpec::Julian(n) => days_from_civil(year, 1, 1) + n as i64,
+            DateSpec::JulianNoLeap(n) => {
+                let mut day = days_from_civil(year, 1, 1) + (n as i64 - 1);
+                if is_leap_year(year) && n > 59 {
+                    day += 1; // Feb 29 is never counted by Jn
+                }
+                day
+            }
+            DateSpec::MonthWeekDay {
+                month,
+                week,
+                weekday,
+            } => {
+                let first_of_month = days_from_civil(year, month as u32, 1);
+                let first_weekday = day_of_week(first_of_month);
+                let mut day = 1 + (weekday as i64 - first_weekday as i64).rem_euclid(7);
+                if week == 5 {
+                    let last_day = days_in_month(year, month as u32) as i64;
+                    while day + 7 <= last_day {
+                        day += 7;
+                    }
+                } else {
+                    day += (week as i64 - 1) * 7;
+                }
+                days_from_civil(year, month as u32, day as u32)
+            }
+        }
+    }
+}
+
+/// Howard Hinnant's `days_from_civil`: days since 1970-01-01 for a
+/// proleptic-Gregorian date. `m` is 1-12, `d` is 1-31.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400; // [0, 399]
+    let mp = (m as i64 + 9) % 12; // [0, 11], Mar-based
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1; // [0, 365]
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy; // [0, 146096]
+    era * 146097 + doe - 719468
+}
+
+/// Inverse of `days_from_civil`: the proleptic-Gregorian `(year, month, day)`
+/// for a day count since 1970-01-01.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11], Mar-based
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+fn is_leap_year(y: i64) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => {
+            if is_leap_year(y) {
+                29
+            } else {
+                28
+            }
+        }
+        _ => 30,
+    }
+}
+
+/// 0 = Sunday, matching the weekday convention used by `Mm.w.d` date specs.
+fn day_of_week(days_since_epoch: i64) -> u32 {
+    (days_since_epoch + 4).rem_euclid(7) as u32
+}
+
+/// Reads a timezone abbreviation: either plain alphabetic characters, or a
+/// `<...>` quoted form (used when the name itself needs digits or a sign,
+/// e.g. `<+14>` or `<-04>`).
+fn parse_name(s: &str) -> anyhow::Result<(String, &str)> {
+    if let Some(rest) = s.strip_prefix('<') {
+        let end = rest
+            .find('>')
+            .ok_or_else(|| anyhow!("unterminated '<' in timezone name '{}'", s))?;
+        return Ok((rest[..end].to_owned(), &rest[end + 1..]));
+    }
+
+    let end = s.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(s.len());
+    if end == 0 {
+        return Err(anyhow!("expected a timezone name in '{}'", s));
+    }
+    Ok((s[..end].to_owned(), &s[end..]))
+}
+
+/// Reads a `[+-]hh[:mm[:ss]]` offset, in seconds, with the sign exactly as
+/// written (i.e. not yet inverted to the `tt_utoff` convention).
+fn parse_offset(s: &str) -> anyhow::Result<(i32, &str)> {
+    let (sign, s) = match s.as_bytes().first() {
+        Some(b'-') => (-1, &s[1..]),
+        Some(b'+') => (1, &s[1..]),
+        _ => (1, s),
+    };
+
+    let (hh, rest) = parse_digits(s)?;
+    let mut seconds = hh as i32 * 3600;
+    let mut rest = rest;
+
+    if let Some(after_colon) = rest.strip_prefix(':') {
+        let (mm, after_mm) = parse_digits(after_colon)?;
+        seconds += mm as i32 * 60;
+        rest = after_mm;
+
+        if let Some(after_colon) = rest.strip_prefix(':') {
+            let (ss, after_ss) = parse_digits(after_colon)?;
+            seconds += ss as i32;
+            rest = after_ss;
+        }
+    }
+
+    Ok((sign * seconds, rest))
+}
+
+/// Reads the same `[+-]hh[:mm[:ss]]` grammar as `parse_offset` but as a
+/// transition time of day, which may be negative or exceed 24h.
+fn parse_time(s: &str) -> anyhow::Result<(i64, &str)> {
+    let (seconds, rest) = parse_offset(s)?;
+    Ok((seconds as i64, rest))
+}
+
+fn parse_digits(s: &str) -> anyhow::Result<(u32, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if end == 0 {
+        return Err(anyhow!("expected a number in '{}'", s));
+    }
+    let value: u32 = s[..end]
+        .parse()
+        .map_err(|err| anyhow!("invalid number '{}': {}", &s[..end], err))?;
+    Ok((value, &s[end..]))
+}
+
+fn parse_date(s: &str) -> anyhow::Result<(DateSpec, &str)> {
+    if let Some(rest) = s.strip_prefix('J') {
+        let (day, rest) = parse_digits(rest)?;
+        return Ok((DateSpec::JulianNoLeap(day as u16), rest));
+    }
+
+    if let Some(rest) = s.strip_prefix('M') {
+        let (month, rest) = parse_digits(rest)?;
+        let rest = rest
+            .strip_prefix('.')
+            .ok_or_else(|| anyhow!("expected '.' after month in '{}'", s))?;
+        let (week, rest) = parse_digits(rest)?;
+        let rest = rest
+            .strip_prefix('.')
+            .ok_or_else(|| anyhow!("expected '.' after week in '{}'", s))?;
+        let (weekday, rest) = parse_digits(rest)?;
+        return Ok((
+            DateSpec::MonthWeekDay {
+                month: month as u8,
+                week: week as u8,
+                weekday: weekday as u8,
+            },
+            rest,
+        ));
+    }
+
+    let (day, rest) = parse_digits(s)?;
+    Ok((DateSpec::Julian(day as u16), rest))
+}
+
+fn parse_transition_date(s: &str) -> anyhow::Result<(TransitionDate, &str)> {
+    let (date, rest) = parse_date(s)?;
+    let (time, rest) = if let Some(after_slash) = rest.strip_prefix('/') {
+        parse_time(after_slash)?
+    } else {
+        (DEFAULT_TRANSITION_TIME, rest)
+    };
+    Ok((TransitionDate { date, time }, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fixed_form_with_no_dst() {
+        let rule = TransitionRule::parse("EST5").unwrap();
+
+        assert_eq!(rule.std_name, "EST");
+        assert_eq!(rule.std_offset, -5 * 3600);
+        assert!(rule.dst.is_none());
+    }
+
+    #[test]
+    fn parses_alternating_form_with_default_dst_offset_and_times() {
+        let rule = TransitionRule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+
+        let dst = rule.dst.as_ref().expect("a DST half");
+        assert_eq!(dst.name, "EDT");
+        // No explicit DST offset: defaults to one hour ahead of std.
+        assert_eq!(dst.offset, rule.std_offset + 3600);
+        assert_eq!(
+            dst.start.date,
+            DateSpec::MonthWeekDay {
+                month: 3,
+                week: 2,
+                weekday: 0
+            }
+        );
+        assert_eq!(dst.start.time, DEFAULT_TRANSITION_TIME);
+        assert_eq!(
+            dst.end.date,
+            DateSpec::MonthWeekDay {
+                month: 11,
+                week: 1,
+                weekday: 0
+            }
+        );
+    }
+
+    #[test]
+    fn parses_explicit_dst_offset_and_transition_times() {
+        let rule = TransitionRule::parse("EST5EDT6,M3.2.0/2,M11.1.0/3:30").unwrap();
+
+        let dst = rule.dst.as_ref().expect("a DST half");
+        assert_eq!(dst.offset, -6 * 3600);
+        assert_eq!(dst.start.time, 2 * 3600);
+        assert_eq!(dst.end.time, 3 * 3600 + 30 * 60);
+    }
+
+    #[test]
+    fn parses_quoted_names_with_signs_and_digits() {
+        let rule = TransitionRule::parse("<+03>-3").unwrap();
+
+        assert_eq!(rule.std_name, "+03");
+        assert_eq!(rule.std_offset, 3 * 3600);
+        assert!(rule.dst.is_none());
+    }
+
+    #[test]
+    fn parses_julian_day_forms() {
+        let (jn, rest) = parse_date("J60").unwrap();
+        assert_eq!(jn, DateSpec::JulianNoLeap(60));
+        assert_eq!(rest, "");
+
+        let (n, rest) = parse_date("59").unwrap();
+        assert_eq!(n, DateSpec::Julian(59));
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn rejects_trailing_garbage() {
+        assert!(TransitionRule::parse("EST5EDT,M3.2.0,M11.1.0,garbage").is_err());
+    }
+
+    #[test]
+    fn is_dst_active_matches_us_eastern_rule_around_transitions() {
+        let rule = TransitionRule::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+
+        // 2024-03-10 is the US "spring forward" date (second Sunday in March).
+        let just_before = 1_710_054_000 - 1; // 2024-03-10 06:59:59 UTC (01:59:59 EST)
+        let just_after = 1_710_054_000; // 2024-03-10 07:00:00 UTC (03:00:00 EDT)
+        assert!(!rule.is_dst_active(just_before));
+        assert!(rule.is_dst_active(just_after));
+
+        // 2024-11-03 is "fall back" (first Sunday in November).
+        let before_fallback = 1_730_613_600 - 1; // 2024-11-03 05:59:59 UTC (01:59:59 EDT)
+        let after_fallback = 1_730_613_600; // 2024-11-03 06:00:00 UTC (01:00:00 EST)
+        assert!(rule.is_dst_active(before_fallback));
+        assert!(!rule.is_dst_active(after_fallback));
+    }
+
+    #[test]
+    fn is_dst_active_handles_southern_hemisphere_wraparound() {
+        // Sydney: DST runs Oct -> Apr, so the active window wraps the year end.
+        let rule = TransitionRule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+
+        assert!(rule.is_dst_active(1_735_700_000)); // 2025-01-01, well within DST
+        assert!(!rule.is_dst_active(1_719_800_000)); // 2024-07-01, southern winter
+    }
+
+    #[test]
+    fn resolve_day_finds_last_sunday_for_week_5() {
+        // M3.5.0 in 2024: the last Sunday of March 2024 is the 31st.
+        let spec = DateSpec::MonthWeekDay {
+            month: 3,
+            week: 5,
+            weekday: 0,
+        };
+        let (year, month, day) = civil_from_days(spec.resolve_day(2024));
+        assert_eq!((year, month, day), (2024, 3, 31));
+    }
+
+    #[test]
+    fn julian_no_leap_never_counts_feb_29() {
+        // Day 60 in a leap year should land on March 1st both with and
+        // without the Feb 29 in the count, since Jn explicitly skips it.
+        let day = DateSpec::JulianNoLeap(60).resolve_day(2024);
+        assert_eq!(civil_from_days(day), (2024, 3, 1));
+    }
+
+    #[test]
+    fn civil_day_conversions_round_trip() {
+        for days in [-719_528, 0, 18_000, 19_723, 100_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn epoch_is_1970_01_01() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+    }
+
+    #[test]
+    fn leap_year_rules_follow_gregorian_exceptions() {
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(2023));
+        assert!(!is_leap_year(1900)); // divisible by 100 but not 400
+        assert!(is_leap_year(2000)); // divisible by 400
+    }
+}